@@ -1,27 +1,193 @@
 use geozero_lib::geometry_reader::GeomReader;
 use std::io::Write;
 
+/// Default max chord-to-arc deviation used when densifying circular arcs into line segments.
+const DEFAULT_ARC_TOLERANCE: f64 = 1e-6;
+
+/// Upper bound on the number of segments [densify_arc] will emit for a single arc.
+///
+/// A tight tolerance on a large-radius arc drives the ideal segment count arbitrarily high;
+/// capping it trades a locally coarser curve for bounded memory and time.
+const MAX_ARC_SEGMENTS: usize = 4096;
+
+/// GeoJSON container a curve geometry densifies into.
+enum CurveWrapper {
+    LineString,
+    MultiLineString,
+    Polygon,
+}
+
+/// Accumulates the densified linear components of a curve geometry so no nested geometry object is
+/// emitted inside another's `coordinates` array.
+struct CurveSink {
+    wrapper: CurveWrapper,
+    /// `idx` of the geometry, used to write its leading comma on flush.
+    idx: usize,
+    /// Finished linear components (one per ring/member line, or one for a LineString).
+    components: Vec<Vec<(f64, f64)>>,
+    /// Points of the primitive currently being read.
+    seg: Vec<(f64, f64)>,
+    /// Whether the current primitive is a circular arc.
+    seg_is_arc: bool,
+    /// Whether a compound curve is open, so its segments share one component.
+    in_compound: bool,
+    /// Whether the sink was opened by a standalone compound curve and flushes on its end.
+    standalone_compound: bool,
+}
+
 struct GeoJsonEmitter<'a, W: Write> {
     out: &'a mut W,
+    /// Max chord-to-arc deviation when sampling circular arcs into straight segments.
+    tolerance: f64,
+    /// Active curve accumulator, set while inside a curve geometry.
+    sink: Option<CurveSink>,
 }
 
 impl<'a, W: Write> GeoJsonEmitter<'a, W> {
     fn new(out: &'a mut W) -> GeoJsonEmitter<'a, W> {
-        GeoJsonEmitter { out }
+        GeoJsonEmitter {
+            out,
+            tolerance: DEFAULT_ARC_TOLERANCE,
+            sink: None,
+        }
+    }
+    /// Emitter that densifies circular arcs so the chord-to-arc deviation stays below `tolerance`.
+    fn with_tolerance(out: &'a mut W, tolerance: f64) -> GeoJsonEmitter<'a, W> {
+        GeoJsonEmitter {
+            out,
+            tolerance,
+            sink: None,
+        }
     }
     fn comma(&mut self, idx: usize) {
         if idx > 0 {
             self.out.write(b",").unwrap();
         }
     }
+    fn coord(&mut self, x: f64, y: f64, idx: usize) {
+        self.comma(idx);
+        self.out.write(&format!("[{},{}]", x, y).as_bytes()).unwrap();
+    }
+
+    // --- Curve densification helpers -------------------------------------------------------
+
+    /// Open a curve accumulator with the given output wrapper.
+    fn curve_begin(&mut self, wrapper: CurveWrapper, idx: usize) {
+        self.sink = Some(CurveSink {
+            wrapper,
+            idx,
+            components: Vec::new(),
+            seg: Vec::new(),
+            seg_is_arc: false,
+            in_compound: false,
+            standalone_compound: false,
+        });
+    }
+
+    /// Start reading a primitive (arc or straight segment) of the active curve.
+    ///
+    /// If no curve is open this is a standalone circular/linear string, so a LineString sink is
+    /// opened implicitly. Outside a compound curve each primitive starts a fresh component (a ring
+    /// or member line); inside one the segments share the open component.
+    fn segment_begin(&mut self, is_arc: bool, idx: usize) {
+        if self.sink.is_none() {
+            self.curve_begin(CurveWrapper::LineString, idx);
+        }
+        let sink = self.sink.as_mut().unwrap();
+        if !sink.in_compound {
+            sink.components.push(Vec::new());
+        }
+        sink.seg.clear();
+        sink.seg_is_arc = is_arc;
+    }
+
+    /// Finish the current primitive, densifying it into the open component.
+    fn segment_end(&mut self) {
+        let tolerance = self.tolerance;
+        let Some(sink) = self.sink.as_mut() else {
+            return;
+        };
+        let pts = densify_polyline(&sink.seg, sink.seg_is_arc, tolerance);
+        let component = sink.components.last_mut().expect("component open");
+        // Skip the first sample when joining segments; it repeats the previous end point.
+        let skip = usize::from(!component.is_empty());
+        component.extend(pts.into_iter().skip(skip));
+        let flush = !sink.in_compound && matches!(sink.wrapper, CurveWrapper::LineString);
+        if flush {
+            self.curve_flush();
+        }
+    }
+
+    /// Emit the accumulated components as their GeoJSON linear equivalent.
+    fn curve_flush(&mut self) {
+        let Some(sink) = self.sink.take() else {
+            return;
+        };
+        self.comma(sink.idx);
+        match sink.wrapper {
+            CurveWrapper::LineString => {
+                self.out.write(br#"{"type": "LineString", "coordinates": ["#).unwrap();
+                self.write_coords(sink.components.first().map(Vec::as_slice).unwrap_or(&[]));
+                self.out.write(b"]}").unwrap();
+            }
+            CurveWrapper::MultiLineString => {
+                self.out
+                    .write(br#"{"type": "MultiLineString", "coordinates": ["#)
+                    .unwrap();
+                self.write_component_array(&sink.components);
+                self.out.write(b"]}").unwrap();
+            }
+            CurveWrapper::Polygon => {
+                self.out.write(br#"{"type": "Polygon", "coordinates": ["#).unwrap();
+                self.write_component_array(&sink.components);
+                self.out.write(b"]}").unwrap();
+            }
+        }
+    }
+
+    fn write_coords(&mut self, coords: &[(f64, f64)]) {
+        for (i, &(x, y)) in coords.iter().enumerate() {
+            self.coord(x, y, i);
+        }
+    }
+
+    fn write_component_array(&mut self, components: &[Vec<(f64, f64)>]) {
+        for (i, component) in components.iter().enumerate() {
+            if i > 0 {
+                self.out.write(b",").unwrap();
+            }
+            self.out.write(b"[").unwrap();
+            self.write_coords(component);
+            self.out.write(b"]").unwrap();
+        }
+    }
+}
+
+/// Densify a primitive into a coordinate list. Straight segments pass through unchanged; arc
+/// segments are a start point followed by `(mid, end)` control-point pairs, one pair per arc.
+fn densify_polyline(pts: &[(f64, f64)], is_arc: bool, tolerance: f64) -> Vec<(f64, f64)> {
+    if !is_arc || pts.len() < 3 {
+        return pts.to_vec();
+    }
+    let mut out = vec![pts[0]];
+    let mut i = 0;
+    while i + 2 < pts.len() {
+        let sampled = densify_arc(pts[i], pts[i + 1], pts[i + 2], tolerance);
+        // Skip the first sample: it coincides with the previous end point.
+        out.extend(sampled.into_iter().skip(1));
+        i += 2;
+    }
+    out
 }
 
 impl<W: Write> GeomReader for GeoJsonEmitter<'_, W> {
     fn pointxy(&mut self, x: f64, y: f64, idx: usize) {
-        self.comma(idx);
-        self.out
-            .write(&format!("[{},{}]", x, y).as_bytes())
-            .unwrap();
+        if let Some(sink) = self.sink.as_mut() {
+            // Inside a curve: buffer the primitive's points for densification on `*_end`.
+            sink.seg.push((x, y));
+            return;
+        }
+        self.coord(x, y, idx);
     }
     fn point_begin(&mut self, idx: usize) {
         self.comma(idx);
@@ -42,12 +208,22 @@ impl<W: Write> GeomReader for GeoJsonEmitter<'_, W> {
         self.out.write(b"]}").unwrap();
     }
     fn line_begin(&mut self, _size: usize, idx: usize) {
+        if self.sink.is_some() {
+            // A straight segment / ring of an enclosing curve geometry; feed the densifier.
+            self.segment_begin(false, idx);
+            return;
+        }
         self.comma(idx);
         self.out
             .write(br#"{"type": "LineString", "coordinates": ["#)
             .unwrap();
     }
-    fn line_end(&mut self, _idx: usize) {
+    fn line_end(&mut self, idx: usize) {
+        if self.sink.is_some() {
+            self.segment_end();
+            return;
+        }
+        let _ = idx;
         self.out.write(b"]}").unwrap();
     }
     fn multiline_begin(&mut self, _size: usize, idx: usize) {
@@ -91,4 +267,149 @@ impl<W: Write> GeomReader for GeoJsonEmitter<'_, W> {
     fn multipoly_end(&mut self) {
         self.out.write(b"]}").unwrap();
     }
+
+    // Circular arcs and SQL-MM Part 3 curves. GeoJSON has no native curve geometry, so each curve
+    // is densified into line segments at `self.tolerance` and emitted as its linear equivalent
+    // (LineString / MultiLineString / Polygon) without nesting geometry objects in `coordinates`.
+    fn circularstring_begin(&mut self, _size: usize, idx: usize) {
+        self.segment_begin(true, idx);
+    }
+    fn circularstring_end(&mut self, _idx: usize) {
+        self.segment_end();
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, idx: usize) {
+        // Open a component shared by the compound curve's straight and arc segments.
+        let standalone = self.sink.is_none();
+        if standalone {
+            self.curve_begin(CurveWrapper::LineString, idx);
+        }
+        let sink = self.sink.as_mut().unwrap();
+        sink.components.push(Vec::new());
+        sink.in_compound = true;
+        sink.standalone_compound = standalone;
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) {
+        if let Some(sink) = self.sink.as_mut() {
+            sink.in_compound = false;
+            if sink.standalone_compound {
+                self.curve_flush();
+            }
+        }
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, idx: usize) {
+        self.curve_begin(CurveWrapper::Polygon, idx);
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) {
+        self.curve_flush();
+    }
+    fn multicurve_begin(&mut self, _size: usize, idx: usize) {
+        self.curve_begin(CurveWrapper::MultiLineString, idx);
+    }
+    fn multicurve_end(&mut self) {
+        self.curve_flush();
+    }
+}
+
+/// Sample points along the circular arc through `start`, `mid` and `end`.
+///
+/// The circle centre and radius are derived from the three control points, then the sweep angle is
+/// stepped so the maximum chord-to-arc deviation stays below `tolerance`. Collinear points (or a
+/// non-positive tolerance) degenerate to the two endpoints. The segment count is capped at
+/// [MAX_ARC_SEGMENTS] so a tiny `tolerance` relative to the arc's radius can't blow up memory.
+fn densify_arc(
+    start: (f64, f64),
+    mid: (f64, f64),
+    end: (f64, f64),
+    tolerance: f64,
+) -> Vec<(f64, f64)> {
+    // Perpendicular-bisector intersection of the chords gives the circle centre.
+    let (ax, ay) = start;
+    let (bx, by) = mid;
+    let (cx, cy) = end;
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    if tolerance <= 0.0 || d.abs() < f64::EPSILON {
+        return vec![start, end];
+    }
+    let a2 = ax * ax + ay * ay;
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let ux = (a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d;
+    let uy = (a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d;
+    let radius = ((ax - ux).powi(2) + (ay - uy).powi(2)).sqrt();
+
+    let angle = |px: f64, py: f64| (py - uy).atan2(px - ux);
+    let a_start = angle(ax, ay);
+    let a_mid = angle(bx, by);
+    let a_end = angle(cx, cy);
+
+    // Choose the sweep direction that passes through the mid point.
+    let sweep = a_end - a_start;
+    let normalize = |mut t: f64| {
+        while t <= -std::f64::consts::PI {
+            t += 2.0 * std::f64::consts::PI;
+        }
+        while t > std::f64::consts::PI {
+            t -= 2.0 * std::f64::consts::PI;
+        }
+        t
+    };
+    let mid_rel = normalize(a_mid - a_start);
+    let short = normalize(sweep);
+    // Take the short way round when the mid point lies on it, otherwise the long way.
+    let sweep = if (mid_rel >= 0.0) == (short >= 0.0) && mid_rel.abs() <= short.abs() {
+        short
+    } else if short >= 0.0 {
+        short - 2.0 * std::f64::consts::PI
+    } else {
+        short + 2.0 * std::f64::consts::PI
+    };
+
+    // Step size so the sagitta radius*(1 - cos(step/2)) stays under the tolerance.
+    let ratio = 1.0 - (tolerance / radius);
+    let step = if ratio <= -1.0 {
+        sweep.abs()
+    } else {
+        2.0 * ratio.clamp(-1.0, 1.0).acos()
+    };
+    let segments = ((sweep.abs() / step).ceil() as usize).clamp(1, MAX_ARC_SEGMENTS);
+
+    let mut out = Vec::with_capacity(segments + 1);
+    for i in 0..=segments {
+        let t = a_start + sweep * (i as f64) / (segments as f64);
+        out.push((ux + radius * t.cos(), uy + radius * t.sin()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn densify_arc_stays_on_the_circle() {
+        // Quarter arc of the unit circle: (1,0) -> (cos45,sin45) -> (0,1).
+        let s = (1.0, 0.0);
+        let m = (0.5f64.sqrt(), 0.5f64.sqrt());
+        let e = (0.0, 1.0);
+        let tol = 1e-4;
+        let pts = densify_arc(s, m, e, tol);
+        assert!(pts.len() > 2, "arc should be densified into several points");
+        assert_eq!(pts.first().copied(), Some(s));
+        for (x, y) in pts {
+            let r = (x * x + y * y).sqrt();
+            assert!((r - 1.0).abs() <= tol, "sample off the circle: r={r}");
+        }
+    }
+
+    #[test]
+    fn collinear_points_degenerate_to_endpoints() {
+        let pts = densify_arc((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), 1e-3);
+        assert_eq!(pts, vec![(0.0, 0.0), (2.0, 0.0)]);
+    }
+
+    #[test]
+    fn straight_segment_passes_through() {
+        let seg = vec![(0.0, 0.0), (1.0, 1.0)];
+        assert_eq!(densify_polyline(&seg, false, 1e-3), seg);
+    }
 }
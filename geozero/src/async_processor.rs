@@ -0,0 +1,337 @@
+//! Async variants of the processing traits for streaming pipelines.
+//!
+//! The core [GeomProcessor](crate::GeomProcessor) and [FeatureProcessor](crate::FeatureProcessor)
+//! traits are synchronous, which blocks integration with async I/O sources. This module mirrors
+//! the full callback set (including the higher-dimension [coordinate](AsyncGeomProcessor::coordinate)
+//! and [empty_point](AsyncGeomProcessor::empty_point) hooks) behind the `async` feature using
+//! [`async_trait`], so a remote or streamed source can be converted into any existing writer
+//! without buffering the whole dataset in memory.
+//!
+//! [`SyncToAsync`] bridges a synchronous processor into the async pipeline, so writers like
+//! [GeoJsonWriter](crate::geojson::GeoJsonWriter) and [WkbWriter](crate::wkb::WkbWriter) can be
+//! fed from an async source unchanged.
+//!
+//! Nothing in this crate drives these traits yet: the [http](crate::http) range reader's
+//! `select_bbox` is a synchronous [FeatureProcessor](crate::FeatureProcessor) consumer, not an
+//! async producer. Wiring it onto [AsyncFeatureProcessor] is left for a follow-up.
+use crate::error::Result;
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor,
+};
+use async_trait::async_trait;
+
+/// Async mirror of [GeomProcessor](crate::GeomProcessor).
+///
+/// Every callback returns a future; defaults are no-ops so implementors override only what they
+/// need, exactly as in the synchronous trait.
+#[async_trait]
+pub trait AsyncGeomProcessor: Send {
+    /// Dimensions of the coordinates emitted by this processor.
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xy()
+    }
+    async fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    /// Coordinate with optional higher dimensions; defaults to [xy](Self::xy) for 2D producers.
+    #[allow(clippy::too_many_arguments)]
+    async fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        _z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.xy(x, y, idx).await
+    }
+    async fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn point_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn tin_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    async fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Async mirror of [PropertyProcessor](crate::PropertyProcessor).
+#[async_trait]
+pub trait AsyncPropertyProcessor: Send {
+    async fn property(&mut self, _idx: usize, _name: &str, _value: &ColumnValue) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Async mirror of [FeatureProcessor](crate::FeatureProcessor).
+#[async_trait]
+pub trait AsyncFeatureProcessor: AsyncGeomProcessor + AsyncPropertyProcessor {
+    async fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+    async fn dataset_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    async fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        Ok(())
+    }
+    async fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        Ok(())
+    }
+    async fn properties_begin(&mut self) -> Result<()> {
+        Ok(())
+    }
+    async fn properties_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    async fn geometry_begin(&mut self) -> Result<()> {
+        Ok(())
+    }
+    async fn geometry_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Adapter bridging a synchronous processor into the async pipeline.
+///
+/// Each async callback forwards directly to the wrapped synchronous processor. Use this to feed an
+/// existing writer from an [AsyncFeatureProcessor] producer.
+pub struct SyncToAsync<P>(pub P);
+
+#[async_trait]
+impl<P: GeomProcessor + Send> AsyncGeomProcessor for SyncToAsync<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.0.dimensions()
+    }
+    async fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.0.xy(x, y, idx)
+    }
+    #[allow(clippy::too_many_arguments)]
+    async fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.0.coordinate(x, y, z, m, t, tm, idx)
+    }
+    async fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.0.empty_point(idx)
+    }
+    async fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.0.point_begin(idx)
+    }
+    async fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.0.point_end(idx)
+    }
+    async fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.0.linestring_begin(tagged, size, idx)
+    }
+    async fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.0.linestring_end(tagged, idx)
+    }
+    async fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.0.polygon_begin(tagged, size, idx)
+    }
+    async fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.0.polygon_end(tagged, idx)
+    }
+    async fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.multipoint_begin(size, idx)
+    }
+    async fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.0.multipoint_end(idx)
+    }
+    async fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.multilinestring_begin(size, idx)
+    }
+    async fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.0.multilinestring_end(idx)
+    }
+    async fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.multipolygon_begin(size, idx)
+    }
+    async fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.0.multipolygon_end(idx)
+    }
+    async fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.geometrycollection_begin(size, idx)
+    }
+    async fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.0.geometrycollection_end(idx)
+    }
+    async fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.circularstring_begin(size, idx)
+    }
+    async fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.0.circularstring_end(idx)
+    }
+    async fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.compoundcurve_begin(size, idx)
+    }
+    async fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.0.compoundcurve_end(idx)
+    }
+    async fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.curvepolygon_begin(size, idx)
+    }
+    async fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.0.curvepolygon_end(idx)
+    }
+    async fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.multicurve_begin(size, idx)
+    }
+    async fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.0.multicurve_end(idx)
+    }
+    async fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.multisurface_begin(size, idx)
+    }
+    async fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.0.multisurface_end(idx)
+    }
+    async fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.0.triangle_begin(tagged, size, idx)
+    }
+    async fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.0.triangle_end(tagged, idx)
+    }
+    async fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.tin_begin(size, idx)
+    }
+    async fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.0.tin_end(idx)
+    }
+    async fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.0.polyhedralsurface_begin(size, idx)
+    }
+    async fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.0.polyhedralsurface_end(idx)
+    }
+}
+
+#[async_trait]
+impl<P: PropertyProcessor + Send> AsyncPropertyProcessor for SyncToAsync<P> {
+    async fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.0.property(idx, name, value)
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProcessor + Send> AsyncFeatureProcessor for SyncToAsync<P> {
+    async fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.0.dataset_begin(name)
+    }
+    async fn dataset_end(&mut self) -> Result<()> {
+        self.0.dataset_end()
+    }
+    async fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.0.feature_begin(idx)
+    }
+    async fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.0.feature_end(idx)
+    }
+    async fn properties_begin(&mut self) -> Result<()> {
+        self.0.properties_begin()
+    }
+    async fn properties_end(&mut self) -> Result<()> {
+        self.0.properties_end()
+    }
+    async fn geometry_begin(&mut self) -> Result<()> {
+        self.0.geometry_begin()
+    }
+    async fn geometry_end(&mut self) -> Result<()> {
+        self.0.geometry_end()
+    }
+}
@@ -0,0 +1,334 @@
+use crate::error::{GeozeroError, Result};
+use crate::{
+    ColumnValue, FeatureProcessor, GeomProcessor, GeometryType, GeozeroDatasource,
+    PropertyProcessor,
+};
+use std::io::Read;
+
+/// Column type tags, matching the writer's schema encoding.
+#[derive(Clone, Copy)]
+pub(crate) enum ColumnType {
+    Bool = 0,
+    Long = 1,
+    Double = 2,
+    String = 3,
+    Binary = 4,
+}
+
+pub(crate) struct Column {
+    pub(crate) name: String,
+    pub(crate) col_type: ColumnType,
+}
+
+/// Parsed FlatGeobuf header.
+pub(crate) struct Header {
+    pub(crate) name: String,
+    pub(crate) feature_count: u64,
+    pub(crate) columns: Vec<Column>,
+    /// Size in bytes of the packed R-tree block that follows the header.
+    pub(crate) index_size: usize,
+    /// Number of bytes consumed by the magic bytes and header.
+    pub(crate) consumed: usize,
+}
+
+/// Reader for the FlatGeobuf binary encoding, mirroring the layout produced by
+/// [FgbWriter](crate::fgb::FgbWriter).
+///
+/// Implements [GeozeroDatasource] so a FlatGeobuf buffer can be streamed into any processor.
+pub struct FgbReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> FgbReader<R> {
+    /// Open a FlatGeobuf source. The magic bytes are validated when the buffer is processed.
+    pub fn open(reader: R) -> Result<Self> {
+        Ok(FgbReader { reader })
+    }
+}
+
+impl<R: Read> GeozeroDatasource for FgbReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        // FlatGeobuf files are random-access by design; read the whole buffer and decode from
+        // slices so the same decoder is shared with the HTTP range reader.
+        let mut buf = Vec::new();
+        self.reader
+            .read_to_end(&mut buf)
+            .map_err(GeozeroError::IoError)?;
+        let header = parse_header(&buf)?;
+
+        // Skip the packed index and the feature-offset table; a local reader walks features in
+        // order and does not need random access.
+        let offsets_size = header.feature_count as usize * 8;
+        let mut pos = header.consumed + header.index_size + offsets_size;
+
+        processor.dataset_begin(Some(&header.name))?;
+        for idx in 0..header.feature_count {
+            processor.feature_begin(idx)?;
+            let consumed = decode_feature(&buf[pos..], &header.columns, processor)?;
+            pos += consumed;
+            processor.feature_end(idx)?;
+        }
+        processor.dataset_end()?;
+        Ok(())
+    }
+}
+
+/// Parse the magic bytes and header from the start of a FlatGeobuf buffer.
+pub(crate) fn parse_header(buf: &[u8]) -> Result<Header> {
+    if buf.len() < 8 || &buf[0..3] != b"fgb" {
+        return Err(GeozeroError::GeometryFormat);
+    }
+    let mut pos = 8;
+    let name = read_string(buf, &mut pos)?;
+    let feature_count = read_u64(buf, &mut pos)?;
+    let column_count = read_u32(buf, &mut pos)? as usize;
+    let mut columns = Vec::with_capacity(column_count);
+    for _ in 0..column_count {
+        let name = read_string(buf, &mut pos)?;
+        let col_type = column_type(read_u8(buf, &mut pos)?)?;
+        columns.push(Column { name, col_type });
+    }
+    // Dataset envelope (4×f64) followed by the index size.
+    pos += 32;
+    let index_size = read_u32(buf, &mut pos)? as usize;
+    Ok(Header {
+        name,
+        feature_count,
+        columns,
+        index_size,
+        consumed: pos,
+    })
+}
+
+/// Decode one feature from the start of `buf`, driving the geometry and property callbacks.
+///
+/// The caller is responsible for `feature_begin`/`feature_end`. Returns the number of bytes
+/// consumed so callers can advance to the next feature.
+pub(crate) fn decode_feature<P: FeatureProcessor>(
+    buf: &[u8],
+    columns: &[Column],
+    processor: &mut P,
+) -> Result<usize> {
+    let mut pos = 0;
+    let geom_type = geometry_type(read_u8(buf, &mut pos)?)?;
+    let ends = read_u32_vec(buf, &mut pos)?;
+    let part_ends = read_u32_vec(buf, &mut pos)?;
+    let coord_count = read_u32(buf, &mut pos)? as usize;
+    let mut xy = Vec::with_capacity(coord_count);
+    for _ in 0..coord_count {
+        xy.push(read_f64(buf, &mut pos)?);
+    }
+    emit_geometry(processor, geom_type, &ends, &part_ends, &xy)?;
+
+    let prop_len = read_u32(buf, &mut pos)? as usize;
+    let prop_buf = read_slice(buf, &mut pos, prop_len)?;
+    processor.properties_begin()?;
+    // Each property is tagged with its column index, so a feature can carry any subset of the
+    // dataset's columns (see FgbWriter::property) rather than exactly `columns.len()` of them.
+    let mut ppos = 0;
+    while ppos < prop_buf.len() {
+        let col_idx = read_u32(prop_buf, &mut ppos)? as usize;
+        let col = columns.get(col_idx).ok_or(GeozeroError::GeometryFormat)?;
+        // The value borrows `prop_buf`, which outlives this loop, so no allocation escapes.
+        let value = decode_value(prop_buf, &mut ppos, col.col_type)?;
+        processor.property(col_idx, &col.name, &value)?;
+    }
+    processor.properties_end()?;
+    Ok(pos)
+}
+
+/// Emit a single ring / linestring spanning coordinate pairs `[from, to)`.
+fn emit_line<P: GeomProcessor>(
+    p: &mut P,
+    xy: &[f64],
+    from: usize,
+    to: usize,
+    tagged: bool,
+    idx: usize,
+) -> Result<()> {
+    if from > to || to * 2 > xy.len() {
+        return Err(GeozeroError::GeometryFormat);
+    }
+    p.linestring_begin(tagged, to - from, idx)?;
+    for (i, pair) in xy[from * 2..to * 2].chunks_exact(2).enumerate() {
+        p.xy(pair[0], pair[1], i)?;
+    }
+    p.linestring_end(tagged, idx)
+}
+
+/// Emit a polygon whose ring end offsets are `ends`, starting at coordinate pair `coord_from`.
+fn emit_polygon<P: GeomProcessor>(
+    p: &mut P,
+    ends: &[u32],
+    xy: &[f64],
+    coord_from: usize,
+    tagged: bool,
+    idx: usize,
+) -> Result<()> {
+    p.polygon_begin(tagged, ends.len().max(1), idx)?;
+    let mut from = coord_from;
+    for (i, end) in ends.iter().enumerate() {
+        emit_line(p, xy, from, *end as usize, false, i)?;
+        from = *end as usize;
+    }
+    p.polygon_end(tagged, idx)
+}
+
+fn emit_geometry<P: GeomProcessor>(
+    processor: &mut P,
+    geom_type: GeometryType,
+    ends: &[u32],
+    part_ends: &[u32],
+    xy: &[f64],
+) -> Result<()> {
+    match geom_type {
+        GeometryType::Point => {
+            if xy.len() < 2 {
+                return Err(GeozeroError::GeometryFormat);
+            }
+            processor.point_begin(0)?;
+            processor.xy(xy[0], xy[1], 0)?;
+            processor.point_end(0)?;
+        }
+        GeometryType::LineString => emit_line(processor, xy, 0, xy.len() / 2, true, 0)?,
+        GeometryType::Polygon => emit_polygon(processor, ends, xy, 0, true, 0)?,
+        GeometryType::MultiPoint => {
+            processor.multipoint_begin(xy.len() / 2, 0)?;
+            for (i, pair) in xy.chunks_exact(2).enumerate() {
+                processor.xy(pair[0], pair[1], i)?;
+            }
+            processor.multipoint_end(0)?;
+        }
+        GeometryType::MultiLineString => {
+            processor.multilinestring_begin(ends.len(), 0)?;
+            let mut from = 0;
+            for (i, end) in ends.iter().enumerate() {
+                emit_line(processor, xy, from, *end as usize, false, i)?;
+                from = *end as usize;
+            }
+            processor.multilinestring_end(0)?;
+        }
+        GeometryType::MultiPolygon => {
+            processor.multipolygon_begin(part_ends.len(), 0)?;
+            let mut ring_from = 0usize;
+            let mut coord_from = 0usize;
+            for (i, part_end) in part_ends.iter().enumerate() {
+                let part_end = *part_end as usize;
+                if ring_from > part_end || part_end > ends.len() {
+                    return Err(GeozeroError::GeometryFormat);
+                }
+                let rings = &ends[ring_from..part_end];
+                emit_polygon(processor, rings, xy, coord_from, false, i)?;
+                if let Some(last) = rings.last() {
+                    coord_from = *last as usize;
+                }
+                ring_from = part_end;
+            }
+            processor.multipolygon_end(0)?;
+        }
+        _ => return Err(GeozeroError::GeometryFormat),
+    }
+    Ok(())
+}
+
+fn column_type(tag: u8) -> Result<ColumnType> {
+    Ok(match tag {
+        0 => ColumnType::Bool,
+        1 => ColumnType::Long,
+        2 => ColumnType::Double,
+        3 => ColumnType::String,
+        4 => ColumnType::Binary,
+        _ => return Err(GeozeroError::GeometryFormat),
+    })
+}
+
+fn geometry_type(tag: u8) -> Result<GeometryType> {
+    Ok(match tag {
+        t if t == GeometryType::Point as u8 => GeometryType::Point,
+        t if t == GeometryType::LineString as u8 => GeometryType::LineString,
+        t if t == GeometryType::Polygon as u8 => GeometryType::Polygon,
+        t if t == GeometryType::MultiPoint as u8 => GeometryType::MultiPoint,
+        t if t == GeometryType::MultiLineString as u8 => GeometryType::MultiLineString,
+        t if t == GeometryType::MultiPolygon as u8 => GeometryType::MultiPolygon,
+        _ => return Err(GeozeroError::GeometryFormat),
+    })
+}
+
+fn decode_value<'a>(
+    buf: &'a [u8],
+    pos: &mut usize,
+    col_type: ColumnType,
+) -> Result<ColumnValue<'a>> {
+    Ok(match col_type {
+        ColumnType::Bool => ColumnValue::Bool(read_u8(buf, pos)? != 0),
+        ColumnType::Long => ColumnValue::Long(read_i64(buf, pos)?),
+        ColumnType::Double => ColumnValue::Double(read_f64(buf, pos)?),
+        ColumnType::String => {
+            let len = read_u32(buf, pos)? as usize;
+            let bytes = read_slice(buf, pos, len)?;
+            let s = std::str::from_utf8(bytes).map_err(|_| GeozeroError::GeometryFormat)?;
+            ColumnValue::String(s)
+        }
+        ColumnType::Binary => {
+            let len = read_u32(buf, pos)? as usize;
+            ColumnValue::Binary(read_slice(buf, pos, len)?)
+        }
+    })
+}
+
+fn read_u8(buf: &[u8], pos: &mut usize) -> Result<u8> {
+    let v = *buf.get(*pos).ok_or(GeozeroError::GeometryFormat)?;
+    *pos += 1;
+    Ok(v)
+}
+
+fn read_u32(buf: &[u8], pos: &mut usize) -> Result<u32> {
+    let end = *pos + 4;
+    let slice = buf.get(*pos..end).ok_or(GeozeroError::GeometryFormat)?;
+    *pos = end;
+    Ok(u32::from_le_bytes(slice.try_into().unwrap()))
+}
+
+fn read_u64(buf: &[u8], pos: &mut usize) -> Result<u64> {
+    let end = *pos + 8;
+    let slice = buf.get(*pos..end).ok_or(GeozeroError::GeometryFormat)?;
+    *pos = end;
+    Ok(u64::from_le_bytes(slice.try_into().unwrap()))
+}
+
+fn read_i64(buf: &[u8], pos: &mut usize) -> Result<i64> {
+    let end = *pos + 8;
+    let slice = buf.get(*pos..end).ok_or(GeozeroError::GeometryFormat)?;
+    *pos = end;
+    Ok(i64::from_le_bytes(slice.try_into().unwrap()))
+}
+
+fn read_f64(buf: &[u8], pos: &mut usize) -> Result<f64> {
+    let end = *pos + 8;
+    let slice = buf.get(*pos..end).ok_or(GeozeroError::GeometryFormat)?;
+    *pos = end;
+    Ok(f64::from_le_bytes(slice.try_into().unwrap()))
+}
+
+fn read_u32_vec(buf: &[u8], pos: &mut usize) -> Result<Vec<u32>> {
+    let count = read_u32(buf, pos)? as usize;
+    let mut v = Vec::with_capacity(count);
+    for _ in 0..count {
+        v.push(read_u32(buf, pos)?);
+    }
+    Ok(v)
+}
+
+fn read_string(buf: &[u8], pos: &mut usize) -> Result<String> {
+    let len = read_u32(buf, pos)? as usize;
+    let slice = read_slice(buf, pos, len)?;
+    String::from_utf8(slice.to_vec()).map_err(|_| GeozeroError::GeometryFormat)
+}
+
+/// Read `len` bytes starting at `*pos`, bounds-checked, advancing `*pos` past them.
+fn read_slice<'a>(buf: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let end = *pos + len;
+    let slice = buf.get(*pos..end).ok_or(GeozeroError::GeometryFormat)?;
+    *pos = end;
+    Ok(slice)
+}
@@ -0,0 +1,389 @@
+use crate::error::Result;
+use crate::spatial_index::{hilbert_sort, NodeItem, PackedRTree, DEFAULT_NODE_SIZE};
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeometryType, PropertyProcessor,
+};
+
+/// Build an index node from a feature's bounding box, pointing at `offset`.
+fn feature_node(feature: &FeatureBuf, offset: u64) -> NodeItem {
+    NodeItem {
+        min_x: feature.bbox[0],
+        min_y: feature.bbox[1],
+        max_x: feature.bbox[2],
+        max_y: feature.bbox[3],
+        offset,
+    }
+}
+
+/// A single feature collected while processing a source.
+#[derive(Default)]
+struct FeatureBuf {
+    geom_type: GeometryType,
+    /// Flat `x, y` coordinate array of the current geometry.
+    xy: Vec<f64>,
+    /// End indices (into `xy` pair count) of each ring / part, FlatGeobuf style.
+    ends: Vec<u32>,
+    /// For (multi)polygons, the end index (into `ends`) of each polygon's ring run, so nested
+    /// MultiPolygon structure survives the round-trip.
+    part_ends: Vec<u32>,
+    /// Serialized property block: a run of `(column index, value)` pairs, one per property this
+    /// feature actually set. Columns the feature omits simply have no entry.
+    properties: Vec<u8>,
+    /// Feature bounding box `[minx, miny, maxx, maxy]`.
+    bbox: [f64; 4],
+}
+
+/// Column of the dataset schema, discovered from the first feature's properties.
+struct Column {
+    name: String,
+    col_type: ColumnType,
+}
+
+/// Column type tags of this module's schema encoding (not FlatGeobuf's own tag values).
+#[derive(Clone, Copy)]
+enum ColumnType {
+    Bool,
+    Long,
+    Double,
+    String,
+    Binary,
+}
+
+/// Writer for the FlatGeobuf binary encoding.
+///
+/// `FgbWriter` implements [FeatureProcessor], [GeomProcessor] and [PropertyProcessor], so it can be
+/// fed from any geozero source. Call [finish](FgbWriter::finish) to obtain the encoded buffer.
+pub struct FgbWriter {
+    dataset_name: String,
+    dims: CoordDimensions,
+    columns: Vec<Column>,
+    features: Vec<FeatureBuf>,
+    /// Feature under construction.
+    current: FeatureBuf,
+    /// Running dataset extent.
+    extent: [f64; 4],
+}
+
+impl FgbWriter {
+    /// Create a writer for a dataset with the given name.
+    pub fn new(dataset_name: &str) -> Result<Self> {
+        Ok(FgbWriter {
+            dataset_name: dataset_name.to_string(),
+            dims: CoordDimensions::xy(),
+            columns: Vec::new(),
+            features: Vec::new(),
+            current: FeatureBuf::default(),
+            extent: [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY],
+        })
+    }
+
+    /// Serialize the collected features to a FlatGeobuf byte vector.
+    ///
+    /// Layout: the magic bytes, the header (name, feature count, schema, dataset envelope and the
+    /// packed-index size), the packed [spatial index](crate::spatial_index), a feature-offset
+    /// table and the length-delimited features. Features are reordered on the Hilbert curve so the
+    /// index offsets match the on-disk order, which is what lets a ranged reader seek directly to
+    /// a matching feature.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        // Order features on the Hilbert curve, then serialize them recording byte offsets.
+        let mut sort_items: Vec<NodeItem> = self
+            .features
+            .iter()
+            .enumerate()
+            .map(|(i, f)| feature_node(f, i as u64))
+            .collect();
+        let mut extent = NodeItem {
+            min_x: f64::INFINITY,
+            min_y: f64::INFINITY,
+            max_x: f64::NEG_INFINITY,
+            max_y: f64::NEG_INFINITY,
+            offset: 0,
+        };
+        for item in &sort_items {
+            extent.min_x = extent.min_x.min(item.min_x);
+            extent.min_y = extent.min_y.min(item.min_y);
+            extent.max_x = extent.max_x.max(item.max_x);
+            extent.max_y = extent.max_y.max(item.max_y);
+        }
+        hilbert_sort(&mut sort_items, &extent);
+
+        let mut feature_bytes = Vec::new();
+        let mut offsets = Vec::with_capacity(self.features.len());
+        let mut index_items = Vec::with_capacity(self.features.len());
+        for item in &sort_items {
+            let feature = &self.features[item.offset as usize];
+            let byte_offset = feature_bytes.len() as u64;
+            offsets.push(byte_offset);
+            index_items.push(feature_node(feature, byte_offset));
+            self.write_feature(&mut feature_bytes, feature);
+        }
+        let index = PackedRTree::build(&index_items, DEFAULT_NODE_SIZE);
+        let index_bytes = index.to_bytes();
+
+        let mut out = Vec::new();
+        // Magic bytes "fgb" + spec version, as in FlatGeobuf files; the rest of the layout is this
+        // module's own.
+        out.extend_from_slice(b"fgb\x03fgb\x00");
+        self.write_header(&mut out, index_bytes.len());
+        out.extend_from_slice(&index_bytes);
+        for offset in &offsets {
+            out.extend_from_slice(&offset.to_le_bytes());
+        }
+        out.extend_from_slice(&feature_bytes);
+        Ok(out)
+    }
+
+    fn write_header(&self, out: &mut Vec<u8>, index_size: usize) {
+        // Self-describing header: name, feature count, schema, envelope and packed-index size,
+        // laid out in a fixed order so a reader can recover them deterministically.
+        write_string(out, &self.dataset_name);
+        out.extend_from_slice(&(self.features.len() as u64).to_le_bytes());
+        out.extend_from_slice(&(self.columns.len() as u32).to_le_bytes());
+        for col in &self.columns {
+            write_string(out, &col.name);
+            out.push(col.col_type as u8);
+        }
+        for v in self.extent {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        out.extend_from_slice(&(index_size as u32).to_le_bytes());
+    }
+
+    fn write_feature(&self, out: &mut Vec<u8>, feature: &FeatureBuf) {
+        out.push(feature.geom_type as u8);
+        out.extend_from_slice(&(feature.ends.len() as u32).to_le_bytes());
+        for end in &feature.ends {
+            out.extend_from_slice(&end.to_le_bytes());
+        }
+        out.extend_from_slice(&(feature.part_ends.len() as u32).to_le_bytes());
+        for end in &feature.part_ends {
+            out.extend_from_slice(&end.to_le_bytes());
+        }
+        out.extend_from_slice(&(feature.xy.len() as u32).to_le_bytes());
+        for v in &feature.xy {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        out.extend_from_slice(&(feature.properties.len() as u32).to_le_bytes());
+        out.extend_from_slice(&feature.properties);
+    }
+
+    fn grow_extent(&mut self, x: f64, y: f64) {
+        self.extent[0] = self.extent[0].min(x);
+        self.extent[1] = self.extent[1].min(y);
+        self.extent[2] = self.extent[2].max(x);
+        self.extent[3] = self.extent[3].max(y);
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+impl GeomProcessor for FgbWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.current.xy.push(x);
+        self.current.xy.push(y);
+        let bbox = &mut self.current.bbox;
+        bbox[0] = bbox[0].min(x);
+        bbox[1] = bbox[1].min(y);
+        bbox[2] = bbox[2].max(x);
+        bbox[3] = bbox[3].max(y);
+        self.grow_extent(x, y);
+        Ok(())
+    }
+
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.current.geom_type = GeometryType::Point;
+        Ok(())
+    }
+
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.current.geom_type = GeometryType::LineString;
+        }
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if !tagged {
+            // Ring of a (multi)polygon: record its end offset in coordinate pairs.
+            self.current.ends.push((self.current.xy.len() / 2) as u32);
+        }
+        Ok(())
+    }
+
+    fn polygon_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.current.geom_type = GeometryType::Polygon;
+        }
+        Ok(())
+    }
+
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if !tagged {
+            // Sub-polygon of a MultiPolygon: mark where this polygon's rings end.
+            self.current.part_ends.push(self.current.ends.len() as u32);
+        }
+        Ok(())
+    }
+
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.current.geom_type = GeometryType::MultiPoint;
+        Ok(())
+    }
+
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.current.geom_type = GeometryType::MultiLineString;
+        Ok(())
+    }
+
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.current.geom_type = GeometryType::MultiPolygon;
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for FgbWriter {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        // Discover the schema from the first feature and reuse it for the rest. A later feature
+        // may omit a column the first feature had: the column index is written alongside the
+        // value so features need not carry every column, and the reader never mis-decodes a gap
+        // as the wrong column's bytes. A property whose index lies beyond the first feature's
+        // schema is not written.
+        if self.features.is_empty() && self.columns.len() == idx {
+            self.columns.push(Column {
+                name: name.to_string(),
+                col_type: column_type(value),
+            });
+        }
+        if idx < self.columns.len() {
+            encode_property(&mut self.current.properties, idx as u32, value);
+        }
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for FgbWriter {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.current = FeatureBuf {
+            bbox: [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY],
+            ..Default::default()
+        };
+        Ok(())
+    }
+
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let feature = std::mem::take(&mut self.current);
+        self.features.push(feature);
+        Ok(())
+    }
+}
+
+fn column_type(value: &ColumnValue) -> ColumnType {
+    match value {
+        ColumnValue::Bool(_) => ColumnType::Bool,
+        ColumnValue::Byte(_)
+        | ColumnValue::UByte(_)
+        | ColumnValue::Short(_)
+        | ColumnValue::UShort(_)
+        | ColumnValue::Int(_)
+        | ColumnValue::UInt(_)
+        | ColumnValue::Long(_)
+        | ColumnValue::ULong(_) => ColumnType::Long,
+        ColumnValue::Float(_) | ColumnValue::Double(_) => ColumnType::Double,
+        ColumnValue::Binary(_) => ColumnType::Binary,
+        _ => ColumnType::String,
+    }
+}
+
+/// Write one property as its column index followed by the encoded value, so a feature can carry
+/// any subset of the dataset's columns without shifting the values that follow it.
+fn encode_property(out: &mut Vec<u8>, col_idx: u32, value: &ColumnValue) {
+    out.extend_from_slice(&col_idx.to_le_bytes());
+    encode_value(out, value);
+}
+
+fn encode_value(out: &mut Vec<u8>, value: &ColumnValue) {
+    match value {
+        ColumnValue::Bool(v) => out.push(*v as u8),
+        ColumnValue::Byte(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::UByte(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::Short(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::UShort(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::Int(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::UInt(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::Long(v) => out.extend_from_slice(&v.to_le_bytes()),
+        ColumnValue::ULong(v) => out.extend_from_slice(&(*v as i64).to_le_bytes()),
+        ColumnValue::Float(v) => out.extend_from_slice(&(*v as f64).to_le_bytes()),
+        ColumnValue::Double(v) => out.extend_from_slice(&v.to_le_bytes()),
+        ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+            write_string(out, v)
+        }
+        ColumnValue::Binary(v) => {
+            out.extend_from_slice(&(v.len() as u32).to_le_bytes());
+            out.extend_from_slice(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fgb::FgbReader;
+    use crate::GeometryType;
+
+    /// Collects the coordinates and string properties replayed by a reader.
+    #[derive(Default)]
+    struct Collector {
+        xy: Vec<(f64, f64)>,
+        props: Vec<String>,
+    }
+
+    impl GeomProcessor for Collector {
+        fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+            self.xy.push((x, y));
+            Ok(())
+        }
+    }
+    impl PropertyProcessor for Collector {
+        fn property(&mut self, _idx: usize, _name: &str, value: &ColumnValue) -> Result<bool> {
+            if let ColumnValue::String(s) = value {
+                self.props.push(s.to_string());
+            }
+            Ok(false)
+        }
+    }
+    impl FeatureProcessor for Collector {}
+
+    #[test]
+    fn roundtrip_polygon_with_property() {
+        let mut writer = FgbWriter::new("test").unwrap();
+        writer.feature_begin(0).unwrap();
+        writer.polygon_begin(true, 1, 0).unwrap();
+        writer.linestring_begin(false, 4, 0).unwrap();
+        for &(x, y) in &[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)] {
+            writer.xy(x, y, 0).unwrap();
+        }
+        writer.linestring_end(false, 0).unwrap();
+        writer.polygon_end(true, 0).unwrap();
+        writer.property(0, "name", &ColumnValue::String("poly")).unwrap();
+        writer.feature_end(0).unwrap();
+        assert_eq!(writer.features.len(), 1);
+        assert_eq!(writer.features[0].geom_type as u8, GeometryType::Polygon as u8);
+        let bytes = writer.finish().unwrap();
+
+        let mut collector = Collector::default();
+        FgbReader::open(&bytes[..])
+            .unwrap()
+            .process(&mut collector)
+            .unwrap();
+        assert_eq!(collector.xy.first(), Some(&(0.0, 0.0)));
+        assert_eq!(collector.xy.len(), 4);
+        assert_eq!(collector.props, vec!["poly".to_string()]);
+    }
+}
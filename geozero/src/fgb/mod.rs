@@ -0,0 +1,47 @@
+//! FlatGeobuf conversion.
+//!
+//! [FlatGeobuf](https://flatgeobuf.org/) is a performant binary encoding for
+//! geographic data based on [flatbuffers](https://google.github.io/flatbuffers/)
+//! that can hold a collection of Simple Features including circular interpolations
+//! as defined by SQL-MM Part 3.
+//!
+//! This module provides a reader implementing [GeozeroDatasource](crate::GeozeroDatasource)
+//! and a [FgbWriter] implementing the processing traits, so that any geozero source can be
+//! round-tripped through a compact binary encoding without depending on the downstream
+//! [flatgeobuf](https://docs.rs/flatgeobuf) crate.
+//!
+//! The encoding is this module's own: the FlatGeobuf magic bytes followed by a little-endian
+//! header, a packed [spatial index](crate::spatial_index), a feature-offset table and
+//! length-prefixed features. It does not use flatbuffers, so its output is not readable by other
+//! FlatGeobuf implementations and [FgbReader] does not read files written by them. Only XY
+//! Point, LineString, Polygon, MultiPoint, MultiLineString and MultiPolygon geometries are
+//! encoded; curve geometries are not.
+pub(crate) mod fgb_reader;
+pub(crate) mod fgb_writer;
+
+pub use fgb_reader::*;
+pub use fgb_writer::*;
+
+pub(crate) mod conversion {
+    use crate::error::Result;
+    use crate::fgb::FgbWriter;
+    use crate::{FeatureProcessor, GeozeroGeometry};
+
+    /// Convert to FlatGeobuf.
+    pub trait ToFgb {
+        /// Convert to a FlatGeobuf byte vector holding a single-feature dataset.
+        fn to_fgb(&self) -> Result<Vec<u8>>;
+    }
+
+    impl<T: GeozeroGeometry> ToFgb for T {
+        fn to_fgb(&self) -> Result<Vec<u8>> {
+            let mut fgb = FgbWriter::new("")?;
+            // Wrap the geometry in a feature so it is flushed into the dataset; `process_geom`
+            // only drives the geometry callbacks, not `feature_begin`/`feature_end`.
+            fgb.feature_begin(0)?;
+            self.process_geom(&mut fgb)?;
+            fgb.feature_end(0)?;
+            Ok(fgb.finish()?)
+        }
+    }
+}
@@ -20,6 +20,7 @@
 //! | Format / trait |            [GeozeroGeometry]            |             [GeozeroDatasource]              |     [GeomProcessor]      | Geometry Conversion |
 //! |----------------|-----------------------------------------|----------------------------------------------|--------------------------|---------------------|
 //! | geo-types      | `geo_types::Geometry<f64>`              | -                                            | [geo_types::GeoWriter]   | [ToGeo]             |
+//! | FlatGeobuf     | -                                       | [fgb::FgbReader]                             | [fgb::FgbWriter]         | [ToFgb]             |
 //! | GeoJSON        | `GeoJson`                               | [geojson::GeoJsonReader], [geojson::GeoJson] | [geojson::GeoJsonWriter] | [ToJson]            |
 //! | GDAL           | `gdal::vector::Geometry`                | -                                            | [gdal::GdalWriter]       | [ToGdal]            |
 //! | GEOS           | `geos::Geometry`                        | -                                            | [geos::GeosWriter]       | [ToGeos]            |
@@ -33,6 +34,12 @@ mod feature_processor;
 mod geometry_processor;
 mod multiplex;
 mod property_processor;
+pub mod spatial_index;
+
+#[cfg(feature = "async")]
+mod async_processor;
+#[cfg(feature = "async")]
+pub use async_processor::*;
 
 pub use api::*;
 pub use feature_processor::*;
@@ -40,6 +47,11 @@ pub use geometry_processor::*;
 pub use multiplex::*;
 pub use property_processor::*;
 
+#[cfg(feature = "with-fgb")]
+pub mod fgb;
+#[cfg(feature = "with-fgb")]
+pub use crate::fgb::conversion::*;
+
 #[cfg(feature = "with-gdal")]
 pub mod gdal;
 #[cfg(feature = "with-gdal")]
@@ -63,6 +75,9 @@ pub use crate::geos::conversion::*;
 #[cfg(feature = "with-gpkg")]
 pub mod gpkg;
 
+#[cfg(feature = "with-http")]
+pub mod http;
+
 #[cfg(any(feature = "with-postgis-postgres", feature = "with-postgis-sqlx"))]
 pub mod postgis;
 
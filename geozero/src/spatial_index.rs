@@ -0,0 +1,414 @@
+//! Static packed Hilbert R-tree spatial index.
+//!
+//! The index is built from a feature collection driven through the
+//! [FeatureProcessor]/[GeomProcessor](crate::GeomProcessor) pipeline, so formats like
+//! [FlatGeobuf](crate::fgb) can expose fast bounding-box selection. Features are sorted on a
+//! Hilbert curve and the tree is packed bottom-up into a flat array of nodes laid out level by
+//! level from root to leaves, so it can be memory-mapped or streamed.
+//!
+//! The on-disk feature order must match the Hilbert order produced here so that the feature
+//! offsets stored in the index stay valid.
+use crate::error::{GeozeroError, Result};
+use crate::{FeatureProcessor, GeomProcessor};
+
+/// Little-endian cursor over a byte slice used when reading a serialized index.
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Cursor { buf, pos: 0 }
+    }
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.pos + n > self.buf.len() {
+            return Err(GeozeroError::GeometryFormat);
+        }
+        let slice = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// Default number of entries per node.
+pub const DEFAULT_NODE_SIZE: usize = 16;
+
+/// Axis-aligned bounding box together with the byte/feature offset of its entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NodeItem {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+    /// Offset of the child node or feature this entry points at.
+    pub offset: u64,
+}
+
+impl NodeItem {
+    fn empty() -> Self {
+        NodeItem {
+            min_x: f64::INFINITY,
+            min_y: f64::INFINITY,
+            max_x: f64::NEG_INFINITY,
+            max_y: f64::NEG_INFINITY,
+            offset: 0,
+        }
+    }
+
+    fn expand(&mut self, other: &NodeItem) {
+        self.min_x = self.min_x.min(other.min_x);
+        self.min_y = self.min_y.min(other.min_y);
+        self.max_x = self.max_x.max(other.max_x);
+        self.max_y = self.max_y.max(other.max_y);
+    }
+
+    fn intersects(&self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> bool {
+        self.min_x <= max_x && self.min_y <= max_y && self.max_x >= min_x && self.max_y >= min_y
+    }
+}
+
+/// A packed Hilbert R-tree over a set of feature bounding boxes.
+pub struct PackedRTree {
+    /// Nodes laid out level by level, root first.
+    nodes: Vec<NodeItem>,
+    node_size: usize,
+    num_leaf_nodes: usize,
+    /// Start index of each level in `nodes`, root level first.
+    level_bounds: Vec<usize>,
+}
+
+impl PackedRTree {
+    /// Build an index over the given leaf bounding boxes, sorting them on the Hilbert curve.
+    ///
+    /// `items[i].offset` is preserved, so the caller can map a query result back to its feature.
+    /// The returned [PackedRTree] reorders the items internally; use [hilbert_sort] first if the
+    /// feature payload on disk must follow the same order.
+    pub fn build(items: &[NodeItem], node_size: usize) -> Self {
+        let node_size = node_size.clamp(2, u16::MAX as usize);
+        let mut extent = NodeItem::empty();
+        for item in items {
+            extent.expand(item);
+        }
+        let mut items = items.to_vec();
+        hilbert_sort(&mut items, &extent);
+
+        let level_bounds = level_bounds(items.len(), node_size);
+        let num_nodes = *level_bounds.last().unwrap_or(&0);
+        let mut nodes = vec![NodeItem::empty(); num_nodes];
+
+        // Leaves occupy the last level.
+        let leaf_start = num_nodes - items.len();
+        nodes[leaf_start..].clone_from_slice(&items);
+
+        // Pack parent levels bottom-up; each parent's bbox is the union of its children and its
+        // offset points at the first child node.
+        for level in (0..level_bounds.len() - 1).rev() {
+            let child_start = level_bounds[level + 1];
+            let child_end = if level + 2 < level_bounds.len() {
+                level_bounds[level + 2]
+            } else {
+                num_nodes
+            };
+            let mut parent = level_bounds[level];
+            let mut child = child_start;
+            while child < child_end {
+                let mut node = NodeItem::empty();
+                node.offset = child as u64;
+                for _ in 0..node_size {
+                    if child >= child_end {
+                        break;
+                    }
+                    node.expand(&nodes[child]);
+                    child += 1;
+                }
+                nodes[parent] = node;
+                parent += 1;
+            }
+        }
+
+        PackedRTree {
+            nodes,
+            node_size,
+            num_leaf_nodes: items.len(),
+            level_bounds,
+        }
+    }
+
+    /// Return the offsets of all features whose bounding box intersects the query rectangle.
+    ///
+    /// The descent is stack-based and skips subtrees that do not intersect the query.
+    pub fn query(&self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Vec<u64> {
+        let mut results = Vec::new();
+        if self.nodes.is_empty() {
+            return results;
+        }
+        let leaf_start = self.nodes.len() - self.num_leaf_nodes;
+        let mut stack = vec![0usize];
+        while let Some(node_index) = stack.pop() {
+            let is_leaf = node_index >= leaf_start;
+            if is_leaf {
+                let node = &self.nodes[node_index];
+                if node.intersects(min_x, min_y, max_x, max_y) {
+                    results.push(node.offset);
+                }
+                continue;
+            }
+            let node = self.nodes[node_index];
+            if !node.intersects(min_x, min_y, max_x, max_y) {
+                continue;
+            }
+            let child_start = node.offset as usize;
+            // Bound the child span by the child level's own boundary, not by `nodes.len()`:
+            // the `node_size` stride would otherwise run past a short level into the next one,
+            // revisiting leaves and yielding duplicate offsets.
+            let level_end = self.level_end(child_start);
+            let child_end = (child_start + self.node_size).min(level_end);
+            for child in child_start..child_end {
+                stack.push(child);
+            }
+        }
+        results
+    }
+
+    /// Serialize the tree to a flat byte buffer so it can be stored alongside the features and
+    /// fetched independently (e.g. via an HTTP range request).
+    ///
+    /// Layout: `node_count` (u32), `node_size` (u16), `num_leaf_nodes` (u32), the level-bounds
+    /// table (u32 count followed by u32 entries) and the nodes as `4×f64 + u64` each.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(14 + self.level_bounds.len() * 4 + self.nodes.len() * 40);
+        out.extend_from_slice(&(self.nodes.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(self.node_size as u16).to_le_bytes());
+        out.extend_from_slice(&(self.num_leaf_nodes as u32).to_le_bytes());
+        out.extend_from_slice(&(self.level_bounds.len() as u32).to_le_bytes());
+        for bound in &self.level_bounds {
+            out.extend_from_slice(&(*bound as u32).to_le_bytes());
+        }
+        for node in &self.nodes {
+            out.extend_from_slice(&node.min_x.to_le_bytes());
+            out.extend_from_slice(&node.min_y.to_le_bytes());
+            out.extend_from_slice(&node.max_x.to_le_bytes());
+            out.extend_from_slice(&node.max_y.to_le_bytes());
+            out.extend_from_slice(&node.offset.to_le_bytes());
+        }
+        out
+    }
+
+    /// Reconstruct a tree from the bytes produced by [to_bytes](PackedRTree::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut c = Cursor::new(bytes);
+        let node_count = c.u32()? as usize;
+        let node_size = c.u16()? as usize;
+        let num_leaf_nodes = c.u32()? as usize;
+        let level_count = c.u32()? as usize;
+        let mut level_bounds = Vec::with_capacity(level_count);
+        for _ in 0..level_count {
+            level_bounds.push(c.u32()? as usize);
+        }
+        let mut nodes = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            nodes.push(NodeItem {
+                min_x: c.f64()?,
+                min_y: c.f64()?,
+                max_x: c.f64()?,
+                max_y: c.f64()?,
+                offset: c.u64()?,
+            });
+        }
+        Ok(PackedRTree {
+            nodes,
+            node_size,
+            num_leaf_nodes,
+            level_bounds,
+        })
+    }
+
+    /// End index (exclusive) of the tree level that contains `index`.
+    fn level_end(&self, index: usize) -> usize {
+        for &bound in &self.level_bounds {
+            if bound > index {
+                return bound;
+            }
+        }
+        self.nodes.len()
+    }
+}
+
+/// Compute the start index of each tree level, root first, plus a trailing total-count entry.
+fn level_bounds(num_items: usize, node_size: usize) -> Vec<usize> {
+    if num_items == 0 {
+        return vec![0];
+    }
+    let mut level_sizes = Vec::new();
+    let mut n = num_items;
+    loop {
+        level_sizes.push(n);
+        if n == 1 {
+            break;
+        }
+        n = n.div_ceil(node_size);
+    }
+    // level_sizes is leaf-first; accumulate offsets root-first.
+    level_sizes.reverse();
+    let mut bounds = Vec::with_capacity(level_sizes.len());
+    let mut offset = 0;
+    for size in &level_sizes {
+        bounds.push(offset);
+        offset += size;
+    }
+    bounds.push(offset);
+    bounds
+}
+
+/// Sort items in place by the Hilbert value of their bounding-box centre, relative to `extent`.
+pub fn hilbert_sort(items: &mut [NodeItem], extent: &NodeItem) {
+    const HILBERT_MAX: f64 = ((1u32 << 16) - 1) as f64;
+    let width = extent.max_x - extent.min_x;
+    let height = extent.max_y - extent.min_y;
+    let scale = |item: &NodeItem| -> u32 {
+        let cx = (item.min_x + item.max_x) / 2.0;
+        let cy = (item.min_y + item.max_y) / 2.0;
+        let x = if width > 0.0 {
+            ((cx - extent.min_x) / width * HILBERT_MAX) as u32
+        } else {
+            0
+        };
+        let y = if height > 0.0 {
+            ((cy - extent.min_y) / height * HILBERT_MAX) as u32
+        } else {
+            0
+        };
+        hilbert_xy2d(x, y)
+    };
+    items.sort_by_key(scale);
+}
+
+/// Map `(x, y)` on a 16-bit grid to its distance along the Hilbert curve.
+///
+/// Standard xy2d rotation loop for an `n = 2^16` side curve.
+pub fn hilbert_xy2d(mut x: u32, mut y: u32) -> u32 {
+    let n: u32 = 1 << 16;
+    let mut d: u32 = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u32::from((x & s) > 0);
+        let ry = u32::from((y & s) > 0);
+        d = d.wrapping_add(s.wrapping_mul(s).wrapping_mul((3 * rx) ^ ry));
+        // Rotate the quadrant.
+        if ry == 0 {
+            if rx == 1 {
+                x = s.wrapping_sub(1).wrapping_sub(x);
+                y = s.wrapping_sub(1).wrapping_sub(y);
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    d
+}
+
+/// Builds a [PackedRTree] from a geozero source in a single processing pass.
+///
+/// Feed this to a datasource's `process`; it records each feature's bounding box via the geometry
+/// callbacks and produces the index when [finish](HilbertIndexBuilder::finish) is called.
+#[derive(Default)]
+pub struct HilbertIndexBuilder {
+    items: Vec<NodeItem>,
+    current: NodeItem,
+    feature_index: u64,
+}
+
+impl HilbertIndexBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        HilbertIndexBuilder::default()
+    }
+
+    /// Build the index with the given node size, consuming the collected bounding boxes.
+    pub fn finish(self, node_size: usize) -> PackedRTree {
+        PackedRTree::build(&self.items, node_size)
+    }
+}
+
+impl GeomProcessor for HilbertIndexBuilder {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.current.min_x = self.current.min_x.min(x);
+        self.current.min_y = self.current.min_y.min(y);
+        self.current.max_x = self.current.max_x.max(x);
+        self.current.max_y = self.current.max_y.max(y);
+        Ok(())
+    }
+}
+
+impl FeatureProcessor for HilbertIndexBuilder {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.feature_index = idx;
+        self.current = NodeItem::empty();
+        Ok(())
+    }
+
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let mut item = self.current;
+        item.offset = self.feature_index;
+        self.items.push(item);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn point_item(x: f64, y: f64, offset: u64) -> NodeItem {
+        NodeItem {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+            offset,
+        }
+    }
+
+    #[test]
+    fn query_multilevel_no_duplicates() {
+        // 100 points on a 10x10 grid forces a tree with 3+ levels at node size 16.
+        let items: Vec<_> = (0..100)
+            .map(|i| point_item((i % 10) as f64, (i / 10) as f64, i))
+            .collect();
+        let tree = PackedRTree::build(&items, DEFAULT_NODE_SIZE);
+
+        let mut hits = tree.query(2.5, 2.5, 5.5, 5.5);
+        hits.sort_unstable();
+        let unique: HashSet<_> = hits.iter().copied().collect();
+        assert_eq!(hits.len(), unique.len(), "query yielded duplicate offsets");
+
+        // Brute-force reference: points with 3<=x<=5 and 3<=y<=5.
+        let expected: HashSet<u64> = items
+            .iter()
+            .filter(|i| i.min_x >= 2.5 && i.min_x <= 5.5 && i.min_y >= 2.5 && i.min_y <= 5.5)
+            .map(|i| i.offset)
+            .collect();
+        assert_eq!(unique, expected);
+    }
+
+    #[test]
+    fn hilbert_is_a_bijection_on_corners() {
+        // Distinct grid cells map to distinct Hilbert distances.
+        assert_ne!(hilbert_xy2d(0, 0), hilbert_xy2d(1, 0));
+        assert_ne!(hilbert_xy2d(0, 0), hilbert_xy2d(0, 1));
+    }
+}